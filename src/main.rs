@@ -1,35 +1,79 @@
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
 use std::process::ExitCode;
+use std::time::Duration;
 
 const DEFAULT_API_URL: &str = "https://api.frankwiles.com/api/storage/create/";
+const DEFAULT_AUTH_URL: &str = "https://api.frankwiles.com/api/auth";
 
 #[derive(Parser, Debug)]
 #[command(name = "store")]
 #[command(about = "Store data in the Frank Wiles API", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
-    /// Data to store: either a JSON string or key=value pairs
-    #[arg(required = true, trailing_var_arg = true)]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Data to store: either a JSON string or key=value pairs.
+    /// Place any flags (--json, --file, --profile, ...) BEFORE the data, as
+    /// everything after the first positional argument is treated as data.
+    #[arg(trailing_var_arg = true)]
     data: Vec<String>,
 
-    /// API token (or set STORE_API_TOKEN env var)
+    /// Bulk import records from a JSON array or NDJSON file (use `-` for stdin)
+    #[arg(long, conflicts_with_all = ["stdin", "data"])]
+    file: Option<String>,
+
+    /// Bulk import records from stdin as a JSON array or NDJSON
+    #[arg(long, conflicts_with = "data")]
+    stdin: bool,
+
+    /// API token (or set STORE_API_TOKEN env var, or run `store login`)
     #[arg(long, env = "STORE_API_TOKEN")]
-    api_token: String,
+    api_token: Option<String>,
 
     /// Project slug (or set STORE_PROJECT env var)
     #[arg(long, env = "STORE_PROJECT")]
-    project: String,
+    project: Option<String>,
 
     /// API URL (or set STORE_API_URL env var)
-    #[arg(long, env = "STORE_API_URL", default_value = DEFAULT_API_URL)]
-    api_url: String,
+    #[arg(long, env = "STORE_API_URL")]
+    api_url: Option<String>,
 
     /// Data type categorization (optional)
     #[arg(long)]
     r#type: Option<String>,
+
+    /// Named profile to read defaults from (see config.toml)
+    #[arg(long, env = "STORE_PROFILE")]
+    profile: Option<String>,
+
+    /// Number of times to retry a transient failure before giving up
+    #[arg(long, default_value_t = 3)]
+    retries: u32,
+
+    /// Base delay in milliseconds for exponential backoff between retries
+    #[arg(long, default_value_t = 500)]
+    retry_base_ms: u64,
+
+    /// Emit results and errors as structured JSON instead of human text
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Authenticate via a device-authorization flow and persist the token
+    Login {
+        /// Base auth URL (or set STORE_AUTH_URL env var)
+        #[arg(long, env = "STORE_AUTH_URL", default_value = DEFAULT_AUTH_URL)]
+        auth_url: String,
+    },
 }
 
 #[derive(Serialize)]
@@ -39,11 +83,229 @@ struct Payload {
     data: serde_json::Value,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Default, Clone, Debug)]
 struct ApiError {
     detail: Option<String>,
     #[serde(default)]
     message: Option<String>,
+    #[serde(default)]
+    code: Option<String>,
+}
+
+/// Typed failures surfaced by `run`, each mapped to a distinct process exit
+/// code so scripts can branch on the kind of failure.
+#[derive(Debug)]
+enum StoreError {
+    Unauthorized(ApiError),
+    Forbidden(ApiError),
+    NotFound(ApiError),
+    BadRequest { detail: String, api: ApiError },
+    ServerError(ApiError),
+    RateLimited(ApiError),
+    Network(String),
+    ParseInput(String),
+    Partial { stored: usize, failed: usize },
+}
+
+impl StoreError {
+    /// Distinct exit code per variant; `0` is reserved for success.
+    fn exit_code(&self) -> u8 {
+        match self {
+            StoreError::ParseInput(_) => 2,
+            StoreError::BadRequest { .. } => 3,
+            StoreError::Unauthorized(_) => 4,
+            StoreError::Forbidden(_) => 5,
+            StoreError::NotFound(_) => 6,
+            StoreError::ServerError(_) => 7,
+            StoreError::Network(_) => 8,
+            StoreError::Partial { .. } => 9,
+            StoreError::RateLimited(_) => 10,
+        }
+    }
+
+    /// Stable machine-readable kind, echoed in `--json` output.
+    fn kind(&self) -> &'static str {
+        match self {
+            StoreError::Unauthorized(_) => "unauthorized",
+            StoreError::Forbidden(_) => "forbidden",
+            StoreError::NotFound(_) => "not_found",
+            StoreError::BadRequest { .. } => "bad_request",
+            StoreError::ServerError(_) => "server_error",
+            StoreError::RateLimited(_) => "rate_limited",
+            StoreError::Network(_) => "network",
+            StoreError::ParseInput(_) => "parse_input",
+            StoreError::Partial { .. } => "partial",
+        }
+    }
+
+    /// Structured representation for `--json` error output, echoing the API
+    /// `code` when the server provided one.
+    fn to_json(&self) -> serde_json::Value {
+        let api = match self {
+            StoreError::Unauthorized(a)
+            | StoreError::Forbidden(a)
+            | StoreError::NotFound(a)
+            | StoreError::ServerError(a)
+            | StoreError::RateLimited(a)
+            | StoreError::BadRequest { api: a, .. } => Some(a),
+            _ => None,
+        };
+        serde_json::json!({
+            "status": "error",
+            "kind": self.kind(),
+            "code": api.and_then(|a| a.code.clone()),
+            "message": self.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for StoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreError::Unauthorized(_) => write!(f, "Unauthorized - check your API token"),
+            StoreError::Forbidden(_) => {
+                write!(f, "Forbidden - you don't have permission for this project")
+            }
+            StoreError::NotFound(_) => write!(f, "Not found - check the API URL and project slug"),
+            StoreError::BadRequest { detail, .. } => write!(f, "Bad request - {}", detail),
+            StoreError::ServerError(_) => write!(f, "Server error - please try again later"),
+            StoreError::RateLimited(_) => {
+                write!(f, "Rate limited - too many requests, please slow down")
+            }
+            StoreError::Network(msg) => write!(f, "Network error - {}", msg),
+            StoreError::ParseInput(msg) => write!(f, "{}", msg),
+            StoreError::Partial { stored, failed } => {
+                write!(f, "{} stored, {} failed", stored, failed)
+            }
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+#[derive(Deserialize)]
+struct AuthorizeResponse {
+    verification_url: String,
+    code: String,
+    exchange_token: String,
+    poll_interval: u64,
+}
+
+#[derive(Serialize)]
+struct ExchangeRequest {
+    exchange_token: String,
+}
+
+#[derive(Deserialize)]
+struct ExchangeResponse {
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// Persisted credentials, read automatically on subsequent invocations.
+#[derive(Serialize, Deserialize, Default)]
+struct Credentials {
+    api_token: Option<String>,
+}
+
+/// A set of named profiles loaded from `config.toml`.
+#[derive(Deserialize, Default)]
+struct Config {
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+}
+
+/// Defaults for a single profile; any unset field leaves the CLI/env value in
+/// place.
+#[derive(Deserialize, Default, Clone)]
+struct Profile {
+    api_url: Option<String>,
+    project: Option<String>,
+    api_token: Option<String>,
+    r#type: Option<String>,
+}
+
+/// Load and merge config from `~/.config/store/config.toml` and `./store.toml`,
+/// with the project-local file taking precedence per profile.
+fn load_config() -> Result<Config> {
+    let mut config = Config::default();
+
+    let mut paths = Vec::new();
+    if let Ok(dir) = config_dir() {
+        paths.push(dir.join("config.toml"));
+    }
+    paths.push(PathBuf::from("store.toml"));
+
+    for path in paths {
+        if !path.exists() {
+            continue;
+        }
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config from '{}'", path.display()))?;
+        let parsed: Config =
+            toml::from_str(&raw).with_context(|| format!("Failed to parse '{}'", path.display()))?;
+        config.profiles.extend(parsed.profiles);
+    }
+
+    Ok(config)
+}
+
+/// Location of the per-user config directory (`~/.config/store`), honoring
+/// `XDG_CONFIG_HOME` when set.
+fn config_dir() -> Result<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+        .context("Could not determine config directory (set HOME or XDG_CONFIG_HOME)")?;
+    Ok(base.join("store"))
+}
+
+fn credentials_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("credentials.toml"))
+}
+
+/// Load a previously persisted token, if any.
+fn load_credentials() -> Result<Credentials> {
+    let path = credentials_path()?;
+    if !path.exists() {
+        return Ok(Credentials::default());
+    }
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read credentials from '{}'", path.display()))?;
+    toml::from_str(&raw).with_context(|| format!("Failed to parse '{}'", path.display()))
+}
+
+fn save_credentials(creds: &Credentials) -> Result<()> {
+    let dir = config_dir()?;
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create '{}'", dir.display()))?;
+    let path = dir.join("credentials.toml");
+    let toml = toml::to_string(creds).context("Failed to serialize credentials")?;
+
+    // The file holds a bearer token; keep it readable only by its owner.
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&path)
+            .with_context(|| format!("Failed to write credentials to '{}'", path.display()))?;
+        file.write_all(toml.as_bytes())
+            .with_context(|| format!("Failed to write credentials to '{}'", path.display()))?;
+        // Tighten an already-existing file whose mode predates this code.
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to secure '{}'", path.display()))?;
+    }
+    #[cfg(not(unix))]
+    std::fs::write(&path, toml)
+        .with_context(|| format!("Failed to write credentials to '{}'", path.display()))?;
+    Ok(())
 }
 
 fn parse_data_input(inputs: &[String]) -> Result<serde_json::Value> {
@@ -78,6 +340,85 @@ fn parse_data_input(inputs: &[String]) -> Result<serde_json::Value> {
     ))
 }
 
+/// Read a set of records from a file path (or `-`/stdin) formatted as either a
+/// top-level JSON array of objects or newline-delimited JSON.
+fn read_records(source: &RecordSource) -> Result<Vec<serde_json::Value>> {
+    let raw = match source {
+        RecordSource::Stdin => {
+            let mut buf = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buf)
+                .context("Failed to read records from stdin")?;
+            buf
+        }
+        RecordSource::File(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read records from '{}'", path))?,
+    };
+
+    // A top-level JSON array is the common case for exports; fall back to
+    // newline-delimited JSON otherwise.
+    if let Ok(serde_json::Value::Array(records)) = serde_json::from_str(&raw) {
+        for (idx, record) in records.iter().enumerate() {
+            if !record.is_object() {
+                anyhow::bail!("Record {} is not a JSON object", idx + 1);
+            }
+        }
+        return Ok(records);
+    }
+
+    let mut records = Vec::new();
+    for (idx, line) in raw.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| format!("Invalid JSON on line {}", idx + 1))?;
+        if !value.is_object() {
+            anyhow::bail!("Record on line {} is not a JSON object", idx + 1);
+        }
+        records.push(value);
+    }
+    Ok(records)
+}
+
+/// Where bulk records should be read from.
+enum RecordSource {
+    Stdin,
+    File(String),
+}
+
+/// How aggressively to retry transient failures.
+#[derive(Clone, Copy)]
+struct RetryConfig {
+    retries: u32,
+    base_ms: u64,
+}
+
+/// Statuses worth retrying; everything else fails fast.
+fn is_retryable(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 500 | 502 | 503 | 504)
+}
+
+/// Exponential backoff (`base * 2^attempt`) with additive jitter bounded by the
+/// base delay, so concurrent clients don't retry in lockstep.
+fn backoff_delay(base_ms: u64, attempt: u32) -> u64 {
+    let factor = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+    let exp = base_ms.saturating_mul(factor);
+    exp.saturating_add(jitter_ms(base_ms))
+}
+
+fn jitter_ms(span: u64) -> u64 {
+    if span == 0 {
+        return 0;
+    }
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+    nanos % span
+}
+
 #[tokio::main]
 async fn main() -> ExitCode {
     // Parse args first, letting clap handle --help and --version
@@ -94,9 +435,31 @@ async fn main() -> ExitCode {
         }
     };
 
+    let json = args.json;
+
     match run(args).await {
         Ok(_) => ExitCode::SUCCESS,
         Err(e) => {
+            // Typed failures carry a distinct exit code and structured JSON;
+            // anything else exits with 1, as JSON under --json and otherwise
+            // as the colorized anyhow chain.
+            if let Some(store_err) = e.downcast_ref::<StoreError>() {
+                if json {
+                    println!("{}", store_err.to_json());
+                } else {
+                    eprintln!("\x1b[31mError:\x1b[0m {}", store_err);
+                }
+                return ExitCode::from(store_err.exit_code());
+            }
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({"status": "error", "kind": "unknown", "message": e.to_string()})
+                );
+                return ExitCode::FAILURE;
+            }
+
             eprintln!("\x1b[31mError:\x1b[0m {}", e);
 
             // Add chain context if available
@@ -112,52 +475,288 @@ async fn main() -> ExitCode {
 }
 
 async fn run(args: Args) -> Result<()> {
-    let data = parse_data_input(&args.data)
-        .context("Failed to parse data input")?;
+    let client = reqwest::Client::new();
 
-    let payload = Payload {
-        project_slug: args.project.clone(),
-        data_type: args.r#type,
-        data,
+    // Subcommand dispatch; the bare invocation stores data.
+    if let Some(Command::Login { auth_url }) = &args.command {
+        return login(&client, auth_url).await;
+    }
+
+    // Resolve configuration with precedence CLI > env > profile > default.
+    // clap already folds CLI over env into each Option, so anything still set
+    // here is explicit and outranks the profile.
+    let profile = match &args.profile {
+        Some(name) => {
+            let config = load_config().map_err(|e| StoreError::ParseInput(format!("{:#}", e)))?;
+            config
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| StoreError::ParseInput(format!("Unknown profile: '{}'", name)))?
+        }
+        None => Profile::default(),
     };
 
-    let client = reqwest::Client::new();
-    let response = client
-        .post(&args.api_url)
-        .bearer_auth(&args.api_token)
-        .json(&payload)
-        .send()
-        .await
-        .context("Failed to send request to API")?;
+    let api_url = args
+        .api_url
+        .clone()
+        .or(profile.api_url)
+        .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+    let data_type = args.r#type.clone().or(profile.r#type);
 
-    let status = response.status();
+    // Token: CLI/env, then profile, then persisted credentials. A corrupt
+    // credentials.toml surfaces its parse error rather than being mistaken for
+    // a missing token.
+    let token = match args.api_token.clone().or(profile.api_token) {
+        Some(token) => token,
+        None => load_credentials()
+            .map_err(|e| StoreError::ParseInput(format!("{:#}", e)))?
+            .api_token
+            .ok_or_else(|| {
+                StoreError::ParseInput(
+                    "No API token. Pass --api-token, set STORE_API_TOKEN, or run `store login`"
+                        .into(),
+                )
+            })?,
+    };
 
-    if status.is_success() {
-        let body = response.text().await.unwrap_or_default();
+    let project = args.project.clone().or(profile.project).ok_or_else(|| {
+        StoreError::ParseInput("No project. Pass --project, set STORE_PROJECT, or use a profile".into())
+    })?;
+
+    let retry = RetryConfig {
+        retries: args.retries,
+        base_ms: args.retry_base_ms,
+    };
+
+    // Bulk import: iterate a file/stdin of records, storing each independently.
+    if args.stdin || args.file.is_some() {
+        let source = if args.stdin || args.file.as_deref() == Some("-") {
+            RecordSource::Stdin
+        } else {
+            RecordSource::File(args.file.clone().unwrap())
+        };
+
+        let records = read_records(&source).map_err(|e| StoreError::ParseInput(e.to_string()))?;
+
+        let mut stored = 0usize;
+        let mut failed = 0usize;
+        for (idx, data) in records.into_iter().enumerate() {
+            let payload = Payload {
+                project_slug: project.clone(),
+                data_type: data_type.clone(),
+                data,
+            };
+
+            match store_payload(&client, &api_url, &token, &payload, retry).await {
+                Ok(_) => {
+                    stored += 1;
+                    if !args.json {
+                        println!("\x1b[32mStored:\x1b[0m record {}", idx + 1);
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    if !args.json {
+                        eprintln!("\x1b[31mFailed:\x1b[0m record {}: {}", idx + 1, e);
+                    }
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(StoreError::Partial { stored, failed }.into());
+        }
+        if args.json {
+            println!(
+                "{}",
+                serde_json::json!({"status": "success", "stored": stored, "failed": failed})
+            );
+        } else {
+            println!("{} stored, {} failed", stored, failed);
+        }
+        return Ok(());
+    }
+
+    if args.data.is_empty() {
+        return Err(StoreError::ParseInput(
+            "No data provided. Pass data as arguments, or use --file/--stdin".into(),
+        )
+        .into());
+    }
+
+    let data =
+        parse_data_input(&args.data).map_err(|e| StoreError::ParseInput(e.to_string()))?;
+
+    let payload = Payload {
+        project_slug: project,
+        data_type,
+        data,
+    };
+
+    let body = store_payload(&client, &api_url, &token, &payload, retry).await?;
+    if args.json {
+        let result: serde_json::Value =
+            serde_json::from_str(&body).unwrap_or(serde_json::Value::Null);
+        println!(
+            "{}",
+            serde_json::json!({"status": "success", "result": result})
+        );
+    } else {
         println!("\x1b[32mSuccess:\x1b[0m Data stored successfully");
         if !body.is_empty() && body != "null" {
             println!("{}", body);
         }
-        Ok(())
-    } else {
-        let error_body = response.text().await.unwrap_or_default();
+    }
+    Ok(())
+}
 
-        // Try to parse API error response
-        let error_msg = if let Ok(api_error) = serde_json::from_str::<ApiError>(&error_body) {
-            api_error.detail.or(api_error.message).unwrap_or(error_body)
-        } else {
-            error_body
-        };
+/// POST a single payload, retrying transient failures, and map a non-success
+/// status onto a typed `StoreError`, returning the response body on success.
+async fn store_payload(
+    client: &reqwest::Client,
+    api_url: &str,
+    token: &str,
+    payload: &Payload,
+    retry: RetryConfig,
+) -> Result<String, StoreError> {
+    let mut attempt = 0u32;
+
+    loop {
+        let result = client
+            .post(api_url)
+            .bearer_auth(token)
+            .json(payload)
+            .send()
+            .await;
 
-        let friendly_status = match status {
-            StatusCode::UNAUTHORIZED => "Unauthorized - check your API token".to_string(),
-            StatusCode::FORBIDDEN => "Forbidden - you don't have permission for this project".to_string(),
-            StatusCode::NOT_FOUND => "Not found - check the API URL and project slug".to_string(),
-            StatusCode::BAD_REQUEST => format!("Bad request - {}", error_msg),
-            StatusCode::INTERNAL_SERVER_ERROR => "Server error - please try again later".to_string(),
-            _ => format!("HTTP {} - {}", status.as_u16(), error_msg),
+        // Transient connection/timeout errors are retryable; anything else
+        // propagates immediately.
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                if attempt < retry.retries && (e.is_connect() || e.is_timeout()) {
+                    let delay = backoff_delay(retry.base_ms, attempt);
+                    attempt += 1;
+                    eprintln!("retrying in {}ms (attempt {}/{})", delay, attempt, retry.retries);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                    continue;
+                }
+                return Err(StoreError::Network(e.to_string()));
+            }
         };
 
-        anyhow::bail!("API request failed: {}", friendly_status)
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response.text().await.unwrap_or_default());
+        }
+
+        if is_retryable(status) && attempt < retry.retries {
+            // Honor an explicit Retry-After on 429/503; otherwise back off.
+            let retry_after = if matches!(status.as_u16(), 429 | 503) {
+                response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<u64>().ok())
+                    .map(|secs| secs * 1000)
+            } else {
+                None
+            };
+            let delay = retry_after.unwrap_or_else(|| backoff_delay(retry.base_ms, attempt));
+            attempt += 1;
+            eprintln!("retrying in {}ms (attempt {}/{})", delay, attempt, retry.retries);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            continue;
+        }
+
+        let error_body = response.text().await.unwrap_or_default();
+
+        // Parse the API `{ code, message }` / `{ detail }` error, keeping the
+        // structured fields so the typed error can echo them.
+        let api = serde_json::from_str::<ApiError>(&error_body).unwrap_or_default();
+        let detail = api
+            .detail
+            .clone()
+            .or_else(|| api.message.clone())
+            .unwrap_or(error_body);
+
+        return Err(match status {
+            StatusCode::UNAUTHORIZED => StoreError::Unauthorized(api),
+            StatusCode::FORBIDDEN => StoreError::Forbidden(api),
+            StatusCode::NOT_FOUND => StoreError::NotFound(api),
+            StatusCode::BAD_REQUEST => StoreError::BadRequest { detail, api },
+            StatusCode::TOO_MANY_REQUESTS => StoreError::RateLimited(api),
+            _ => StoreError::ServerError(api),
+        });
+    }
+}
+
+/// Run the device-authorization flow: request a code, have the user approve it
+/// in a browser, poll for the issued token, and persist it.
+async fn login(client: &reqwest::Client, auth_url: &str) -> Result<()> {
+    let base = auth_url.trim_end_matches('/');
+
+    let authorize: AuthorizeResponse = client
+        .post(format!("{}/authorize", base))
+        .send()
+        .await
+        .context("Failed to start authorization")?
+        .error_for_status()
+        .context("Authorization request was rejected")?
+        .json()
+        .await
+        .context("Failed to parse authorization response")?;
+
+    println!(
+        "To authorize, visit \x1b[36m{}\x1b[0m and enter code \x1b[1m{}\x1b[0m",
+        authorize.verification_url, authorize.code
+    );
+    println!("Waiting for approval...");
+
+    let request = ExchangeRequest {
+        exchange_token: authorize.exchange_token,
+    };
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(authorize.poll_interval)).await;
+
+        let response = client
+            .post(format!("{}/token/exchange", base))
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to poll for token")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let error_msg = if let Ok(api_error) = serde_json::from_str::<ApiError>(&error_body) {
+                api_error.detail.or(api_error.message).unwrap_or(error_body)
+            } else {
+                error_body
+            };
+            anyhow::bail!("Authorization failed: {}", error_msg);
+        }
+
+        let exchange: ExchangeResponse = response
+            .json()
+            .await
+            .context("Failed to parse token response")?;
+
+        if let Some(token) = exchange.token {
+            save_credentials(&Credentials {
+                api_token: Some(token),
+            })?;
+            println!(
+                "\x1b[32mSuccess:\x1b[0m logged in, credentials saved to {}",
+                credentials_path()?.display()
+            );
+            return Ok(());
+        }
+
+        // Any non-token response (e.g. a "pending" status) means keep polling.
+        let _ = exchange.status;
     }
 }